@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+const CONFIG_FILE_NAME: &str = "config.json";
+
+/// User-controlled startup behavior, persisted as JSON in `app_data_dir`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Config {
+    pub open_on_startup: Option<bool>,
+    pub startup_minimized: Option<bool>,
+    pub run_in_background: Option<bool>,
+}
+
+impl Config {
+    fn path(app_data_dir: &Path) -> PathBuf {
+        app_data_dir.join(CONFIG_FILE_NAME)
+    }
+
+    /// Whether a config file has ever been persisted, i.e. this isn't the
+    /// first launch since installing/updating to a version that has one.
+    pub fn exists(app_data_dir: &Path) -> bool {
+        Self::path(app_data_dir).exists()
+    }
+
+    /// Load the config from disk, falling back to defaults if it doesn't
+    /// exist yet or fails to parse.
+    pub fn load(app_data_dir: &Path) -> Self {
+        std::fs::read_to_string(Self::path(app_data_dir))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, app_data_dir: &Path) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(Self::path(app_data_dir), json).map_err(|e| e.to_string())
+    }
+
+    pub fn open_on_startup(&self) -> bool {
+        self.open_on_startup.unwrap_or(false)
+    }
+
+    pub fn startup_minimized(&self) -> bool {
+        self.startup_minimized.unwrap_or(false)
+    }
+
+    pub fn run_in_background(&self) -> bool {
+        self.run_in_background.unwrap_or(false)
+    }
+}
+
+fn app_data_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    app.path().app_data_dir().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_config(app: tauri::AppHandle) -> Result<Config, String> {
+    Ok(Config::load(&app_data_dir(&app)?))
+}
+
+#[tauri::command]
+pub fn set_config(app: tauri::AppHandle, config: Config) -> Result<(), String> {
+    config.save(&app_data_dir(&app)?)?;
+    // Note: `startup_minimized` only controls the `--minimized` arg baked into
+    // the autostart launch agent when the plugin is (re-)registered in
+    // `run()`'s `setup`, so flipping it here takes effect on next restart, not
+    // immediately. `open_on_startup`, applied below, is fully live.
+    apply_autostart(&app, &config)
+}
+
+/// Enable or disable the OS autostart registration to match `config`, so
+/// toggling "open on startup" in settings takes effect without a restart.
+#[cfg(any(target_os = "macos", windows, target_os = "linux"))]
+pub fn apply_autostart(app: &tauri::AppHandle, config: &Config) -> Result<(), String> {
+    use tauri_plugin_autostart::ManagerExt;
+
+    let autostart = app.autolaunch();
+    let enabled = autostart.is_enabled().map_err(|e| e.to_string())?;
+    if config.open_on_startup() && !enabled {
+        autostart.enable().map_err(|e| e.to_string())?;
+    } else if !config.open_on_startup() && enabled {
+        autostart.disable().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "macos", windows, target_os = "linux")))]
+pub fn apply_autostart(_app: &tauri::AppHandle, _config: &Config) -> Result<(), String> {
+    Ok(())
+}
+
+/// Read the current OS autostart registration, used to seed `open_on_startup`
+/// on the very first launch after installing/updating to a version that
+/// persists this config, so an existing launch agent isn't silently disabled.
+#[cfg(any(target_os = "macos", windows, target_os = "linux"))]
+pub fn seed_open_on_startup(app: &tauri::AppHandle) -> bool {
+    use tauri_plugin_autostart::ManagerExt;
+    app.autolaunch().is_enabled().unwrap_or(false)
+}
+
+/// Toggle OS autostart registration live, independent of `set_config`, so the
+/// frontend can flip just this setting without writing the full config.
+#[tauri::command]
+pub fn set_autostart_enabled(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+    let dir = app_data_dir(&app)?;
+    let mut config = Config::load(&dir);
+    config.open_on_startup = Some(enabled);
+    config.save(&dir)?;
+    apply_autostart(&app, &config)
+}
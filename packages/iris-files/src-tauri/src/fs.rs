@@ -0,0 +1,142 @@
+use serde::Serialize;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// Metadata for a single entry returned by [`list_dir`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntryMetadata {
+    pub name: String,
+    pub path: String,
+    pub size: u64,
+    pub is_directory: bool,
+    pub is_file: bool,
+    pub is_symlink: bool,
+    pub child_count: Option<u64>,
+    pub permissions: String,
+    pub created_at: Option<i64>,
+    pub modified_at: Option<i64>,
+    pub accessed_at: Option<i64>,
+}
+
+fn epoch_millis(time: std::io::Result<std::time::SystemTime>) -> Option<i64> {
+    time.ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_millis() as i64)
+}
+
+/// Format a Unix file mode as `0755 rwxr-xr-x`.
+#[cfg(unix)]
+fn format_permissions(mode: u32) -> String {
+    let perm_bits = mode & 0o777;
+    let mut flags = String::with_capacity(9);
+    for (i, c) in ['r', 'w', 'x'].iter().cycle().take(9).enumerate() {
+        let shift = 8 - i;
+        if perm_bits & (1 << shift) != 0 {
+            flags.push(*c);
+        } else {
+            flags.push('-');
+        }
+    }
+    format!("{:04o} {}", perm_bits, flags)
+}
+
+fn child_count(path: &Path) -> Option<u64> {
+    std::fs::read_dir(path)
+        .ok()
+        .map(|entries| entries.filter_map(Result::ok).count() as u64)
+}
+
+/// List the contents of `path`, returning metadata for each readable entry.
+///
+/// Entries whose metadata can't be read are skipped rather than aborting the
+/// whole listing. A broken symlink is still listed, with the link's own metadata.
+#[tauri::command]
+pub fn list_dir(path: String) -> Result<Vec<EntryMetadata>, String> {
+    let dir = Path::new(&path);
+    let entries = std::fs::read_dir(dir).map_err(|e| e.to_string())?;
+
+    let mut result = Vec::new();
+    for entry in entries.filter_map(Result::ok) {
+        let entry_path = entry.path();
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+
+        // `entry.metadata()` doesn't follow symlinks, so a symlink to a
+        // directory would otherwise report as neither a file nor a directory.
+        // Resolve against the link target for that decision, falling back to
+        // the link's own metadata if the target is broken/unreachable.
+        let is_symlink = metadata.is_symlink();
+        let target_metadata = if is_symlink {
+            std::fs::metadata(&entry_path).unwrap_or_else(|_| metadata.clone())
+        } else {
+            metadata.clone()
+        };
+        let is_directory = target_metadata.is_dir();
+        #[cfg(unix)]
+        let permissions = {
+            use std::os::unix::fs::PermissionsExt;
+            format_permissions(metadata.permissions().mode())
+        };
+        #[cfg(not(unix))]
+        let permissions = if metadata.permissions().readonly() {
+            "r--r--r--".to_string()
+        } else {
+            "rw-rw-rw-".to_string()
+        };
+
+        result.push(EntryMetadata {
+            name: entry.file_name().to_string_lossy().into_owned(),
+            path: entry_path.to_string_lossy().into_owned(),
+            size: metadata.len(),
+            is_directory,
+            is_file: target_metadata.is_file(),
+            is_symlink,
+            child_count: if is_directory {
+                child_count(&entry_path)
+            } else {
+                None
+            },
+            permissions,
+            created_at: epoch_millis(metadata.created()),
+            modified_at: epoch_millis(metadata.modified()),
+            accessed_at: epoch_millis(metadata.accessed()),
+        });
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn format_permissions_formats_common_modes() {
+        assert_eq!(format_permissions(0o755), "0755 rwxr-xr-x");
+        assert_eq!(format_permissions(0o644), "0644 rw-r--r--");
+        assert_eq!(format_permissions(0o000), "0000 ---------");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn format_permissions_masks_out_non_permission_bits() {
+        // e.g. setuid/setgid/sticky bits above the low 9 must not leak through
+        assert_eq!(format_permissions(0o4755), "0755 rwxr-xr-x");
+    }
+
+    #[test]
+    fn epoch_millis_converts_unix_epoch_relative_time() {
+        let time = UNIX_EPOCH + std::time::Duration::from_millis(1_500);
+        assert_eq!(epoch_millis(Ok(time)), Some(1_500));
+    }
+
+    #[test]
+    fn epoch_millis_returns_none_on_err() {
+        let err = std::io::Error::new(std::io::ErrorKind::Unsupported, "no timestamp");
+        assert_eq!(epoch_millis(Err(err)), None);
+    }
+}
@@ -1,7 +1,19 @@
-use tauri::Manager;
+use tauri::{Emitter, Manager};
+#[cfg(any(target_os = "macos", windows, target_os = "linux"))]
+use tauri::{
+    menu::{Menu, MenuItem},
+    tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
+};
+use tauri_plugin_window_state::{AppHandleExt as _, StateFlags};
 use tracing::info;
 use tracing_subscriber::EnvFilter;
 
+mod config;
+mod fs;
+
+/// Shared with `reset_window_layout` so the two can never drift apart.
+const WINDOW_STATE_FILENAME: &str = "window-state.json";
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     // Initialize tracing with env filter (RUST_LOG=iris_files=debug)
@@ -12,8 +24,52 @@ pub fn run() {
         )
         .init();
 
-    tauri::Builder::default()
+    let builder = tauri::Builder::default();
+
+    // Register the single-instance plugin first so it can intercept relaunches
+    // before any other plugin has a chance to run - desktop only
+    #[cfg(any(target_os = "macos", windows, target_os = "linux"))]
+    let builder = builder.plugin(tauri_plugin_single_instance::init(|app, argv, cwd| {
+        info!("Second instance launched with args: {:?}, cwd: {:?}", argv, cwd);
+
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.show();
+            let _ = window.unminimize();
+            let _ = window.set_focus();
+            #[cfg(target_os = "macos")]
+            sync_dock_visibility(app, true);
+        }
+
+        let paths: Vec<String> = argv
+            .into_iter()
+            .skip(1)
+            .filter(|arg| arg != "--minimized")
+            .collect();
+        if !paths.is_empty() {
+            let _ = app.emit("open-paths", paths);
+        }
+    }));
+
+    // Remember window size/position/maximized state across launches - desktop only
+    #[cfg(any(target_os = "macos", windows, target_os = "linux"))]
+    let builder = builder.plugin(
+        tauri_plugin_window_state::Builder::default()
+            .with_state_flags(StateFlags::all())
+            .with_filename(WINDOW_STATE_FILENAME)
+            .build(),
+    );
+
+    builder
         .plugin(tauri_plugin_os::init())
+        .invoke_handler(tauri::generate_handler![
+            save_window_layout,
+            reset_window_layout,
+            fs::list_dir,
+            config::get_config,
+            config::set_config,
+            config::set_autostart_enabled,
+            set_background_mode
+        ])
         .setup(|app| {
             let data_dir = app
                 .path()
@@ -23,16 +79,71 @@ pub fn run() {
 
             info!("App data directory: {:?}", data_dir);
 
-            // Check if launched with --minimized flag (from autostart) - desktop only
+            let config_existed = config::Config::exists(&data_dir);
+            let mut config = config::Config::load(&data_dir);
+
+            // Start hidden to tray if the persisted config requests it, falling
+            // back to the legacy --minimized CLI arg for the current launch
             #[cfg(any(target_os = "macos", windows, target_os = "linux"))]
             {
-                let args: Vec<String> = std::env::args().collect();
-                if args.contains(&"--minimized".to_string()) {
+                let minimized =
+                    config.startup_minimized() || std::env::args().any(|a| a == "--minimized");
+                if minimized {
                     if let Some(window) = app.get_webview_window("main") {
-                        let _ = window.minimize();
-                        info!("Started minimized (autostart)");
+                        let _ = window.hide();
+                        info!("Started hidden to tray (config/autostart)");
                     }
                 }
+
+                // Hide the Dock icon on macOS when starting as a background sync
+                // daemon, so a minimized/tray launch doesn't clutter the Dock
+                #[cfg(target_os = "macos")]
+                if minimized || config.run_in_background() {
+                    let _ = app.set_activation_policy(tauri::ActivationPolicy::Accessory);
+                }
+            }
+
+            // Build the system tray so the sync daemon can live in the background
+            // instead of exiting or cluttering the taskbar - desktop only
+            #[cfg(any(target_os = "macos", windows, target_os = "linux"))]
+            {
+                let show_hide = MenuItem::with_id(app, "show_hide", "Show/Hide", true, None::<&str>)?;
+                let pause_sync = MenuItem::with_id(app, "pause_sync", "Pause Sync", true, None::<&str>)?;
+                let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
+                let tray_menu = Menu::with_items(app, &[&show_hide, &pause_sync, &quit])?;
+
+                TrayIconBuilder::new()
+                    .icon(app.default_window_icon().ok_or("no default window icon")?.clone())
+                    .menu(&tray_menu)
+                    .on_menu_event(|app, event| match event.id.as_ref() {
+                        "show_hide" => toggle_main_window(app),
+                        "pause_sync" => info!("Pause Sync requested from tray"),
+                        "quit" => app.exit(0),
+                        _ => {}
+                    })
+                    .on_tray_icon_event(|tray, event| {
+                        if let TrayIconEvent::Click {
+                            button: MouseButton::Left,
+                            button_state: MouseButtonState::Up,
+                            ..
+                        } = event
+                        {
+                            toggle_main_window(tray.app_handle());
+                        }
+                    })
+                    .build(app)?;
+
+                // Hide to tray instead of exiting when the main window is closed
+                if let Some(window) = app.get_webview_window("main") {
+                    window.clone().on_window_event(move |event| {
+                        if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                            api.prevent_close();
+                            let _ = window.hide();
+                            #[cfg(target_os = "macos")]
+                            sync_dock_visibility(&window.app_handle(), false);
+                        }
+                    });
+                }
             }
 
             // Add notification plugin
@@ -44,15 +155,122 @@ pub fn run() {
             // Add dialog plugin for file operations
             app.handle().plugin(tauri_plugin_dialog::init())?;
 
-            // Add autostart plugin for desktop platforms
+            // Add autostart plugin for desktop platforms, wired to the persisted
+            // config instead of being unconditionally enabled with hardcoded args
             #[cfg(any(target_os = "macos", windows, target_os = "linux"))]
-            app.handle().plugin(tauri_plugin_autostart::init(
-                tauri_plugin_autostart::MacosLauncher::LaunchAgent,
-                Some(vec!["--minimized"]),
-            ))?;
+            {
+                let autostart_args = if config.startup_minimized() {
+                    Some(vec!["--minimized"])
+                } else {
+                    None
+                };
+                app.handle().plugin(tauri_plugin_autostart::init(
+                    tauri_plugin_autostart::MacosLauncher::LaunchAgent,
+                    autostart_args,
+                ))?;
+
+                if config_existed {
+                    config::apply_autostart(&app.handle(), &config)?;
+                } else {
+                    // First launch after installing/updating to a version that
+                    // persists this config: seed `open_on_startup` from the
+                    // actual OS registration instead of assuming disabled, so
+                    // a launch agent registered by an older version isn't
+                    // silently disabled out from under the user.
+                    config.open_on_startup = Some(config::seed_open_on_startup(&app.handle()));
+                    config.save(&data_dir)?;
+                }
+            }
 
             Ok(())
         })
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+/// Explicitly persist the current window layout, bypassing the plugin's
+/// save-on-close timing so the frontend can offer an immediate "save layout" action.
+#[tauri::command]
+fn save_window_layout(app: tauri::AppHandle) -> Result<(), String> {
+    app.save_window_state(StateFlags::all())
+        .map_err(|e| e.to_string())
+}
+
+/// Delete the persisted window state file so the app reopens at its default
+/// size and position next launch - used by the "reset window layout" setting.
+#[tauri::command]
+fn reset_window_layout(app: tauri::AppHandle) -> Result<(), String> {
+    let state_file = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?
+        .join(WINDOW_STATE_FILENAME);
+    if state_file.exists() {
+        std::fs::remove_file(state_file).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Show and focus the main window if it's hidden, otherwise hide it to the tray.
+#[cfg(any(target_os = "macos", windows, target_os = "linux"))]
+fn toggle_main_window(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let visible = window.is_visible().unwrap_or(false);
+        if visible {
+            let _ = window.hide();
+            #[cfg(target_os = "macos")]
+            sync_dock_visibility(app, false);
+        } else {
+            let _ = window.show();
+            let _ = window.set_focus();
+            #[cfg(target_os = "macos")]
+            sync_dock_visibility(app, true);
+        }
+    }
+}
+
+/// Keep the Dock icon in sync with whether the main window is shown: always
+/// restore it when the window is explicitly shown (the user wants a normal
+/// app session again), and re-hide it when the window is hidden/closed to
+/// tray if the persisted config says the app should run in the background.
+#[cfg(target_os = "macos")]
+fn sync_dock_visibility(app: &tauri::AppHandle, window_visible: bool) {
+    if window_visible {
+        let _ = app.set_activation_policy(tauri::ActivationPolicy::Regular);
+        return;
+    }
+
+    let background = app
+        .path()
+        .app_data_dir()
+        .map(|dir| config::Config::load(&dir).run_in_background())
+        .unwrap_or(false);
+    if background {
+        let _ = app.set_activation_policy(tauri::ActivationPolicy::Accessory);
+    }
+}
+
+/// Flip whether the app behaves as a background sync agent: persists the
+/// choice to config and, on macOS, shows/hides the Dock icon immediately.
+#[tauri::command]
+fn set_background_mode(app: tauri::AppHandle, background: bool) -> Result<(), String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| e.to_string())?;
+    let mut cfg = config::Config::load(&dir);
+    cfg.run_in_background = Some(background);
+    cfg.save(&dir)?;
+
+    #[cfg(target_os = "macos")]
+    {
+        let policy = if background {
+            tauri::ActivationPolicy::Accessory
+        } else {
+            tauri::ActivationPolicy::Regular
+        };
+        let _ = app.set_activation_policy(policy);
+    }
+
+    Ok(())
+}